@@ -0,0 +1,364 @@
+//! Offline ingestion of a MediaWiki `pages-articles` XML dump into a local
+//! word index, so `look_up_all` can resolve most words from memory instead
+//! of scraping `en.wiktionary.org` one word at a time.
+//!
+//! `pages-articles` dumps ship each page's raw wikitext (`==French==`,
+//! `{{fr-noun|m}}`, `# gloss`, `#: {{ux|fr|...}}`, …), not rendered HTML, so
+//! this can't reuse `parse_word_page`/`fetch_language_sections` — those walk
+//! a `tl` DOM built from the `div.mw-parser-output`/`h2`/`span.mw-headline`
+//! shape `wiktionary_lookup` gets back from a live page fetch. Ingestion gets
+//! its own line-oriented wikitext parser below. The dump itself is read with
+//! a streaming `quick_xml` reader over a buffered file handle rather than
+//! loaded into one in-memory DOM, since real Wiktionary dumps run into the
+//! hundreds of megabytes.
+
+use crate::lookup::{strategy_for, Language, Meaning, NounGender, PartOfSpeech, ResultOrError, Word};
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io::BufReader;
+
+/// Parse a `=={level}==`-style wikitext heading line into `(level, title)`,
+/// e.g. `"===Noun==="` -> `(3, "Noun")`. Returns `None` for non-heading lines.
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim();
+    let level = trimmed.chars().take_while(|&c| c == '=').count();
+    if level < 2 || trimmed.len() < level * 2 {
+        return None;
+    }
+    let inner = &trimmed[level..trimmed.len() - level];
+    if inner.is_empty() || inner.starts_with('=') || inner.ends_with('=') {
+        return None;
+    }
+    Some((level, inner.trim()))
+}
+
+/// Strip the wiki markup `parse_heading`'s body lines carry down to plain
+/// text: `[[target|display]]`/`[[target]]` links, `''italic''`/`'''bold'''`
+/// runs, and qualifier templates like `{{lb|fr|transitive}}`.
+fn strip_wikitext_markup(text: &str) -> String {
+    let link_re = Regex::new(r"\[\[(?:[^\]|]*\|)?([^\]]*)\]\]").unwrap();
+    let template_re = Regex::new(r"\{\{[^}]*\}\}").unwrap();
+    let quote_re = Regex::new(r"'{2,}").unwrap();
+    let text = link_re.replace_all(text, "$1");
+    let text = template_re.replace_all(&text, "");
+    quote_re.replace_all(&text, "").trim().to_string()
+}
+
+/// Parse a `{{ux|fr|Orig text|Translation}}`/`{{uxi|...}}` usage-example
+/// template, or failing that fall back to treating the whole line as plain
+/// text with no translation.
+fn parse_example_line(line: &str) -> Option<(String, Option<String>)> {
+    let content = line.trim_start_matches(['#', ':', '*']).trim();
+    let template_re = Regex::new(r"\{\{u(?:x|xi)\|([^}]*)\}\}").unwrap();
+    if let Some(caps) = template_re.captures(content) {
+        let positional: Vec<&str> = caps[1].split('|').filter(|part| !part.contains('=')).collect();
+        // positional[0] is the language code; [1] is the example, [2] its translation.
+        let orig = strip_wikitext_markup(positional.get(1)?);
+        let trans = positional
+            .get(2)
+            .map(|s| strip_wikitext_markup(s))
+            .filter(|s| !s.is_empty());
+        return (!orig.is_empty()).then_some((orig, trans));
+    }
+    let orig = strip_wikitext_markup(content);
+    (!orig.is_empty()).then_some((orig, None))
+}
+
+/// Parse the `# gloss` / `#: example` / `#* example` lines of a POS
+/// subsection into `Meaning`s, each tagged with the same `pos`.
+fn parse_glosses(lines: &[&str], pos: PartOfSpeech) -> Vec<Meaning> {
+    let mut meanings = Vec::new();
+    let mut idx = 0;
+    while idx < lines.len() {
+        let line = lines[idx].trim();
+        if line.starts_with("# ") || line == "#" {
+            let meaning = strip_wikitext_markup(line.trim_start_matches('#'));
+            idx += 1;
+            let mut examples = Vec::new();
+            while idx < lines.len() {
+                let example_line = lines[idx].trim();
+                if example_line.starts_with("#:") || example_line.starts_with("#*") {
+                    examples.extend(parse_example_line(example_line));
+                    idx += 1;
+                } else {
+                    break;
+                }
+            }
+            if !meaning.is_empty() {
+                meanings.push(Meaning {
+                    pos: pos.clone(),
+                    meaning,
+                    examples,
+                });
+            }
+        } else {
+            idx += 1;
+        }
+    }
+    meanings
+}
+
+/// Find a `{{xx-noun|m}}`/`{{xx-noun|f}}`-style headword template in a POS
+/// subsection and read off its gender.
+fn parse_gender_template(lines: &[&str]) -> Option<NounGender> {
+    let re = Regex::new(r"\{\{[a-z]{2,3}-noun\|([mf])").unwrap();
+    lines.iter().find_map(|line| {
+        re.captures(line).map(|caps| match &caps[1] {
+            "m" => NounGender::Masculine,
+            _ => NounGender::Feminine,
+        })
+    })
+}
+
+/// Parse a single page's wikitext (the dump's `<text>` for that `<page>`)
+/// into a `Word`. Dump-sourced entries carry no `Pronunciation`: a Commons
+/// audio file name in the wikitext (`{{audio|fr|File:...|...}}`) isn't a
+/// fetchable URL without resolving it against Commons' MD5-hash upload path,
+/// so words resolved from the index have no IPA or audio; only words fetched
+/// live through `wiktionary_lookup` carry a `Pronunciation`.
+fn parse_wikitext_page(word: &str, lang: Language, wikitext: &str, wiki_link: String) -> ResultOrError<Word> {
+    let strategy = strategy_for(lang);
+    let pos_header_names = strategy.pos_header_names();
+    let lines: Vec<&str> = wikitext.lines().collect();
+
+    let lang_start = lines
+        .iter()
+        .position(|line| parse_heading(line) == Some((2, strategy.headline())))
+        .ok_or_else(|| format!("Cannot find word {word}"))?;
+    let lang_end = lines[lang_start + 1..]
+        .iter()
+        .position(|line| matches!(parse_heading(line), Some((2, _))))
+        .map(|offset| lang_start + 1 + offset)
+        .unwrap_or(lines.len());
+    let section = &lines[lang_start + 1..lang_end];
+
+    let mut meanings = Vec::<Meaning>::new();
+    let mut idx = 0;
+    while idx < section.len() {
+        let (level, name) = match parse_heading(section[idx]) {
+            Some(heading) => heading,
+            None => {
+                idx += 1;
+                continue;
+            }
+        };
+        idx += 1;
+        let sub_end = section[idx..]
+            .iter()
+            .position(|line| matches!(parse_heading(line), Some((l, _)) if l <= level))
+            .map(|offset| idx + offset)
+            .unwrap_or(section.len());
+        let sub_section = &section[idx..sub_end];
+        match name {
+            "Noun" => {
+                let gender = parse_gender_template(sub_section);
+                meanings.extend(parse_glosses(sub_section, PartOfSpeech::Noun { gender }));
+            }
+            "Adjective" => {
+                let pos = PartOfSpeech::Adjective {
+                    f: None,
+                    mp: None,
+                    fp: None,
+                };
+                meanings.extend(parse_glosses(sub_section, pos));
+            }
+            _ if pos_header_names.contains_key(name) => {
+                let pos = pos_header_names[name].clone();
+                meanings.extend(parse_glosses(sub_section, pos));
+            }
+            _ => (),
+        }
+        idx = sub_end;
+    }
+    Ok(Word {
+        word: word.to_owned(),
+        wiki_link,
+        pronunciation: None,
+        meanings,
+    })
+}
+
+/// Stream a `pages-articles` dump page by page and run each page's body
+/// through [`parse_wikitext_page`], keyed by page title. Pages that don't
+/// have a matching `lang` section, or otherwise fail to parse, are skipped
+/// rather than aborting the whole dump.
+pub fn build_index_from_dump(dump_path: &str, lang: Language) -> ResultOrError<HashMap<String, Word>> {
+    let mut reader = Reader::from_reader(BufReader::new(File::open(dump_path)?));
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    let mut index = HashMap::new();
+
+    let mut in_title = false;
+    let mut in_text = false;
+    let mut title: Option<String> = None;
+    let mut body: Option<String> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Eof => break,
+            Event::Start(tag) => match tag.name().as_ref() {
+                b"page" => {
+                    title = None;
+                    body = None;
+                }
+                b"title" => in_title = true,
+                b"text" => in_text = true,
+                _ => (),
+            },
+            Event::Text(text) => {
+                if in_title {
+                    title = Some(text.unescape()?.into_owned());
+                } else if in_text {
+                    body = Some(text.unescape()?.into_owned());
+                }
+            }
+            Event::End(tag) => match tag.name().as_ref() {
+                b"title" => in_title = false,
+                b"text" => in_text = false,
+                b"page" => {
+                    if let (Some(title), Some(body)) = (title.take(), body.take()) {
+                        let wiki_link = format!(
+                            "https://en.wiktionary.org/wiki/{title}#{}",
+                            strategy_for(lang).headline()
+                        );
+                        if let Ok(word) = parse_wikitext_page(&title, lang, &body, wiki_link) {
+                            index.insert(title, word);
+                        }
+                    }
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+        buf.clear();
+    }
+    Ok(index)
+}
+
+pub fn save_index(index: &HashMap<String, Word>, path: &str) -> ResultOrError<()> {
+    fs::write(path, serde_json::to_string(index)?)?;
+    Ok(())
+}
+
+pub fn load_index(path: &str) -> ResultOrError<HashMap<String, Word>> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_heading_reads_level_and_title() {
+        assert_eq!(parse_heading("==French=="), Some((2, "French")));
+        assert_eq!(parse_heading("===Noun==="), Some((3, "Noun")));
+        assert_eq!(parse_heading("  ====Declension====  "), Some((4, "Declension")));
+    }
+
+    #[test]
+    fn parse_heading_rejects_non_headings() {
+        assert_eq!(parse_heading("Not a heading"), None);
+        assert_eq!(parse_heading("=Too short="), None);
+        assert_eq!(parse_heading("===="), None);
+        assert_eq!(parse_heading("{{ux|fr|chat|cat}}"), None);
+    }
+
+    #[test]
+    fn parse_example_line_reads_ux_template() {
+        assert_eq!(
+            parse_example_line("#: {{ux|fr|Le chat dort|The cat sleeps}}"),
+            Some(("Le chat dort".to_string(), Some("The cat sleeps".to_string())))
+        );
+    }
+
+    #[test]
+    fn parse_example_line_falls_back_to_plain_text() {
+        assert_eq!(
+            parse_example_line("#* ''Le chat dort.''"),
+            Some(("Le chat dort.".to_string(), None))
+        );
+    }
+
+    #[test]
+    fn parse_example_line_skips_blank_examples() {
+        assert_eq!(parse_example_line("#:"), None);
+    }
+
+    #[test]
+    fn parse_gender_template_reads_masculine_and_feminine() {
+        assert_eq!(
+            parse_gender_template(&["{{fr-noun|m}}", "# gloss"]),
+            Some(NounGender::Masculine)
+        );
+        assert_eq!(
+            parse_gender_template(&["{{es-noun|f}}", "# gloss"]),
+            Some(NounGender::Feminine)
+        );
+        assert_eq!(parse_gender_template(&["# gloss, no headword template"]), None);
+    }
+
+    #[test]
+    fn parse_glosses_pairs_each_gloss_with_its_own_examples() {
+        let lines = [
+            "# A small domesticated feline",
+            "#: {{ux|fr|Le chat dort|The cat sleeps}}",
+            "#* ''Le chat miaule.''",
+            "# A sly person",
+        ];
+        let meanings = parse_glosses(&lines, PartOfSpeech::Noun { gender: None });
+        assert_eq!(meanings.len(), 2);
+        assert_eq!(meanings[0].meaning, "A small domesticated feline");
+        assert_eq!(
+            meanings[0].examples,
+            vec![
+                ("Le chat dort".to_string(), Some("The cat sleeps".to_string())),
+                ("Le chat miaule.".to_string(), None),
+            ]
+        );
+        assert_eq!(meanings[1].meaning, "A sly person");
+        assert!(meanings[1].examples.is_empty());
+    }
+
+    #[test]
+    fn parse_wikitext_page_finds_the_right_language_section_and_pos_boundary() {
+        let wikitext = "\
+==French==
+===Noun===
+{{fr-noun|m}}
+# A small domesticated feline
+#: {{ux|fr|Le chat dort|The cat sleeps}}
+
+===Verb===
+# to cat around (not a real sense, just a boundary test)
+
+==Spanish==
+===Noun===
+{{es-noun|m}}
+# should not be picked up, wrong language section
+";
+        let word = parse_wikitext_page("chat", Language::French, wikitext, "link".to_string()).unwrap();
+        assert_eq!(word.word, "chat");
+        assert!(word.pronunciation.is_none());
+        assert_eq!(word.meanings.len(), 2);
+        assert_eq!(word.meanings[0].meaning, "A small domesticated feline");
+        assert!(matches!(
+            word.meanings[0].pos,
+            PartOfSpeech::Noun {
+                gender: Some(NounGender::Masculine)
+            }
+        ));
+        assert_eq!(word.meanings[1].meaning, "to cat around (not a real sense, just a boundary test)");
+        assert!(matches!(word.meanings[1].pos, PartOfSpeech::Verb));
+    }
+
+    #[test]
+    fn parse_wikitext_page_errors_when_language_section_is_missing() {
+        let wikitext = "==Spanish==\n===Noun===\n# gato\n";
+        assert!(parse_wikitext_page("chat", Language::French, wikitext, "link".to_string()).is_err());
+    }
+}
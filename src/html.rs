@@ -0,0 +1,144 @@
+//! Renders a browsable HTML review deck alongside the TSV/JSON output, the
+//! same data `word_to_anki_fields` turns into Anki card fields, so a learner
+//! can preview the deck in a browser without importing it first.
+//!
+//! Scoped down from datagengo's `format.rs`: this writes one flat page with
+//! every card concatenated in schedule order, not a paginated, index-linked
+//! set of batch pages. Fine for the deck sizes this tool currently targets;
+//! revisit with real pagination if decks grow large enough to need it.
+
+use crate::lookup::{match_meaning, strategy_for, Example, Language, ResultOrError, Word};
+use std::collections::HashSet;
+use std::fs;
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Wrap each alphanumeric run in `text` in a `<span>` tagged `known` or
+/// `unknown` depending on whether its lowercased form is in `vocab`, so a
+/// learner can see at a glance which words in an example they already study.
+fn tag_tokens(text: &str, vocab: &HashSet<String>) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let token_len = rest
+            .char_indices()
+            .take_while(|(_, c)| c.is_alphanumeric())
+            .last()
+            .map(|(i, c)| i + c.len_utf8());
+        match token_len {
+            Some(len) => {
+                let token = &rest[..len];
+                let class = if vocab.contains(&token.to_lowercase()) {
+                    "known"
+                } else {
+                    "unknown"
+                };
+                html.push_str(&format!("<span class=\"{class}\">{}</span>", escape_html(token)));
+                rest = &rest[len..];
+            }
+            None => {
+                let non_token_len = rest
+                    .char_indices()
+                    .find(|(_, c)| c.is_alphanumeric())
+                    .map(|(i, _)| i)
+                    .unwrap_or(rest.len());
+                html.push_str(&escape_html(&rest[..non_token_len]));
+                rest = &rest[non_token_len..];
+            }
+        }
+    }
+    html
+}
+
+fn render_card(
+    word: &Word,
+    select_meaning: &str,
+    chosen_example: &Option<Example>,
+    audio_dir: &str,
+    lang: Language,
+    vocab: &HashSet<String>,
+) -> String {
+    let meaning = match match_meaning(&word.word, &word.meanings, select_meaning) {
+        Ok(meaning) => meaning,
+        Err(_) => return String::new(),
+    };
+    let headword = strategy_for(lang).format_article(&word.word, meaning);
+    let headword = if headword.is_empty() { word.word.clone() } else { headword };
+    let audio_html = match &word.pronunciation {
+        Some(pronunciation) => format!(
+            "<p class=\"ipa\">{}</p><audio controls src=\"{audio_dir}/{}.mp3\"></audio>",
+            escape_html(&pronunciation.ipa),
+            word.word
+        ),
+        None => String::new(),
+    };
+    let example_html = match chosen_example {
+        Some((orig, trans)) => format!(
+            "<p class=\"example\">{}</p><p class=\"translation\">{}</p>",
+            tag_tokens(orig, vocab),
+            trans.as_deref().map(escape_html).unwrap_or_default()
+        ),
+        None => String::new(),
+    };
+    format!(
+        "<div class=\"card\">\n\
+         <p class=\"headword\"><a href=\"{}\">{}</a></p>\n\
+         {audio_html}\n\
+         <p class=\"meaning\">{}</p>\n\
+         {example_html}\n\
+         </div>",
+        word.wiki_link,
+        escape_html(&headword),
+        escape_html(&meaning.meaning),
+    )
+}
+
+/// Write `cards` (already in graduated schedule order, each a looked-up
+/// `Word` paired with the meaning and example chosen for it) out as a single
+/// styled HTML page at `path`. `vocab` is the full set of lemmas across the
+/// user's word lists, used to color-code example tokens.
+pub fn write_deck(
+    path: &str,
+    audio_dir: &str,
+    lang: Language,
+    cards: &[(Word, String, Option<Example>)],
+    vocab: &HashSet<String>,
+) -> ResultOrError<()> {
+    let cards_html = cards
+        .iter()
+        .map(|(word, select_meaning, chosen_example)| {
+            render_card(word, select_meaning, chosen_example, audio_dir, lang, vocab)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let page = format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>Review deck</title>\n\
+         <style>\n\
+         body {{ font-family: sans-serif; max-width: 40rem; margin: 2rem auto; }}\n\
+         .card {{ border: 1px solid #ccc; border-radius: 8px; padding: 1rem; margin-bottom: 1rem; }}\n\
+         .headword {{ font-size: 1.3rem; font-weight: bold; }}\n\
+         .headword a {{ color: inherit; text-decoration: none; }}\n\
+         .ipa {{ color: #666; }}\n\
+         .translation {{ color: #666; font-style: italic; }}\n\
+         .known {{ color: #2a7a2a; }}\n\
+         .unknown {{ color: #c23; font-weight: bold; }}\n\
+         </style>\n\
+         </head>\n\
+         <body>\n\
+         <h1>Review deck</h1>\n\
+         {cards_html}\n\
+         </body>\n\
+         </html>\n"
+    );
+    fs::write(path, page)?;
+    Ok(())
+}
@@ -35,6 +35,178 @@ pub enum PartOfSpeech {
     Interjection,
 }
 
+/// Wiktionary entries are organized per-language under an `h2` headline
+/// (e.g. "French", "== Spanish =="). `Language` picks which headline to
+/// read and carries the language-specific bits (article rules, the set of
+/// recognized POS header names) via [`strategy_for`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+pub enum Language {
+    French,
+    Spanish,
+    Italian,
+    German,
+    /// Wiktionary also has an "English" `h2` section on every entry page, so
+    /// this works as a scrape target like the others; it's mainly used as
+    /// the translation target in [`crate::translate`].
+    English,
+}
+
+/// The language-specific pieces of the Wiktionary scraping pipeline: which
+/// `h2` section to read, which extra POS header names it recognizes beyond
+/// the shared `Noun`/`Adjective` handling, and how to prefix a headword
+/// with its article for display. `pos_header_names` defaults to the set
+/// shared by every language Wiktionary currently models identically;
+/// override it for a language whose POS headers actually differ.
+pub trait LanguageStrategy {
+    fn headline(&self) -> &'static str;
+
+    fn pos_header_names(&self) -> HashMap<&'static str, PartOfSpeech> {
+        HashMap::from([
+            ("Verb", PartOfSpeech::Verb),
+            ("Pronoun", PartOfSpeech::Pronoun),
+            ("Adverb", PartOfSpeech::Adverb),
+            ("Numeral", PartOfSpeech::Numeral),
+            ("Determiner", PartOfSpeech::Determiner),
+            ("Preposition", PartOfSpeech::Preposition),
+            ("Interjection", PartOfSpeech::Interjection),
+        ])
+    }
+
+    fn format_article(&self, word: &str, meaning: &Meaning) -> String;
+}
+
+struct French;
+
+impl LanguageStrategy for French {
+    fn headline(&self) -> &'static str {
+        "French"
+    }
+
+    fn format_article(&self, word: &str, meaning: &Meaning) -> String {
+        let first_ch = word.chars().nth(0).expect("Word is empty");
+        let is_vowel = match first_ch {
+            'a' | 'e' | 'i' | 'o' | 'u' | 'h' => true,
+            _ => false,
+        };
+        let is_masc = match meaning.pos {
+            PartOfSpeech::Noun {
+                gender: Some(NounGender::Masculine),
+            } => true,
+            PartOfSpeech::Noun {
+                gender: Some(NounGender::Feminine),
+            } => false,
+            _ => return "".to_string(),
+        };
+        match (is_vowel, is_masc) {
+            (true, true) => format!("l'{word} (masc.)"),
+            (true, false) => format!("l'{word} (fem.)"),
+            (false, true) => format!("le {word}"),
+            (false, false) => format!("la {word}"),
+        }
+    }
+}
+
+struct Spanish;
+
+impl LanguageStrategy for Spanish {
+    fn headline(&self) -> &'static str {
+        "Spanish"
+    }
+
+    fn format_article(&self, word: &str, meaning: &Meaning) -> String {
+        let is_masc = match meaning.pos {
+            PartOfSpeech::Noun {
+                gender: Some(NounGender::Masculine),
+            } => true,
+            PartOfSpeech::Noun {
+                gender: Some(NounGender::Feminine),
+            } => false,
+            _ => return "".to_string(),
+        };
+        if is_masc {
+            format!("el {word}")
+        } else {
+            format!("la {word}")
+        }
+    }
+}
+
+struct Italian;
+
+impl LanguageStrategy for Italian {
+    fn headline(&self) -> &'static str {
+        "Italian"
+    }
+
+    fn format_article(&self, word: &str, meaning: &Meaning) -> String {
+        let first_ch = word.chars().nth(0).expect("Word is empty");
+        let is_vowel = matches!(first_ch, 'a' | 'e' | 'i' | 'o' | 'u');
+        let is_masc = match meaning.pos {
+            PartOfSpeech::Noun {
+                gender: Some(NounGender::Masculine),
+            } => true,
+            PartOfSpeech::Noun {
+                gender: Some(NounGender::Feminine),
+            } => false,
+            _ => return "".to_string(),
+        };
+        match (is_vowel, is_masc) {
+            (true, true) => format!("l'{word} (masc.)"),
+            (true, false) => format!("l'{word} (fem.)"),
+            (false, true) => format!("il {word}"),
+            (false, false) => format!("la {word}"),
+        }
+    }
+}
+
+struct German;
+
+impl LanguageStrategy for German {
+    fn headline(&self) -> &'static str {
+        "German"
+    }
+
+    fn format_article(&self, word: &str, meaning: &Meaning) -> String {
+        let is_masc = match meaning.pos {
+            PartOfSpeech::Noun {
+                gender: Some(NounGender::Masculine),
+            } => true,
+            PartOfSpeech::Noun {
+                gender: Some(NounGender::Feminine),
+            } => false,
+            _ => return "".to_string(),
+        };
+        if is_masc {
+            format!("der {word}")
+        } else {
+            format!("die {word}")
+        }
+    }
+}
+
+struct English;
+
+impl LanguageStrategy for English {
+    fn headline(&self) -> &'static str {
+        "English"
+    }
+
+    fn format_article(&self, _word: &str, _meaning: &Meaning) -> String {
+        // English nouns aren't gendered, so there's no article to prefix.
+        "".to_string()
+    }
+}
+
+pub fn strategy_for(lang: Language) -> Box<dyn LanguageStrategy> {
+    match lang {
+        Language::French => Box::new(French),
+        Language::Spanish => Box::new(Spanish),
+        Language::Italian => Box::new(Italian),
+        Language::German => Box::new(German),
+        Language::English => Box::new(English),
+    }
+}
+
 pub type Example = (String, Option<String>);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,13 +216,13 @@ pub struct Meaning {
     pub examples: Vec<Example>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pronunciation {
     pub ipa: String,
     pub audio_url: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Word {
     pub word: String,
     pub wiki_link: String,
@@ -140,9 +312,10 @@ fn split_at_h3_h4<'a, 'b>(nodes: &'b [&'a Node<'a>]) -> Vec<Vec<&'a Node<'a>>> {
         .collect()
 }
 
-fn fetch_french_sections<'a>(
+fn fetch_language_sections<'a>(
     dom: &'a tl::VDom,
     parser: &'a Parser,
+    headline: &str,
 ) -> Option<Vec<Vec<&'a Node<'a>>>> {
     let mw_parser_output = dom
         .query_selector("div.mw-parser-output")
@@ -151,8 +324,8 @@ fn fetch_french_sections<'a>(
         .get(parser)
         .unwrap();
     let body_elems = get_children(parser, mw_parser_output)?;
-    let french_tags = split_and_take(parser, &body_elems, "h2", "French")?;
-    Some(split_at_h3_h4(french_tags))
+    let lang_tags = split_and_take(parser, &body_elems, "h2", headline)?;
+    Some(split_at_h3_h4(lang_tags))
 }
 
 fn find_first_node_by_name<'a>(nodes: &[&'a Node<'a>], node_name: &str) -> Option<&'a Node<'a>> {
@@ -294,8 +467,44 @@ fn get_adj_form_from_section(parser: &Parser, section: &Vec<&Node>) -> Option<Pa
     Some(PartOfSpeech::Adjective { f, mp, fp })
 }
 
-pub async fn request_w_header(url: &str) -> ResultOrError<reqwest::Response> {
-    let client = reqwest::Client::new();
+/// Find the `Meaning` among `meanings` whose gloss contains `select_meaning`.
+/// Logs and errors if none match; logs (but doesn't error) if more than one does,
+/// picking the last match.
+pub fn match_meaning<'a>(
+    word: &str,
+    meanings: &'a Vec<Meaning>,
+    select_meaning: &str,
+) -> ResultOrError<&'a Meaning> {
+    let mut meaning = None;
+    let mut is_ambiguous = false;
+    let all_meanings_str = meanings
+        .iter()
+        .map(|m| "  ".to_string() + &m.meaning)
+        .collect::<Vec<_>>()
+        .join("\n");
+    for meaning_ in meanings {
+        let is_match = meaning_.meaning.contains(select_meaning);
+        if is_match {
+            if meaning.is_some() {
+                is_ambiguous = true;
+            }
+            meaning = Some(meaning_);
+        }
+    }
+    if is_ambiguous {
+        println!("Ambiguous meaning '{select_meaning}' for word '{word}'; choose from \n{all_meanings_str}\n");
+    }
+    match meaning {
+        Some(meaning) => Ok(meaning),
+        None => {
+            println!(
+                "No meaning of '{word}' matches the given meaning '{select_meaning}'. Select from:\n{all_meanings_str}");
+            Err(format!("No matching meaning for '{word}'"))?
+        }
+    }
+}
+
+pub async fn request_w_header(client: &reqwest::Client, url: &str) -> ResultOrError<reqwest::Response> {
     Ok(client
         .get(url)
         .header(
@@ -306,23 +515,17 @@ pub async fn request_w_header(url: &str) -> ResultOrError<reqwest::Response> {
         .await?)
 }
 
-pub async fn wiktionary_lookup(word: &str) -> ResultOrError<Word> {
-    let part_of_speech_name: HashMap<&str, PartOfSpeech> = HashMap::from([
-        ("Verb", PartOfSpeech::Verb),
-        ("Pronoun", PartOfSpeech::Pronoun),
-        ("Adverb", PartOfSpeech::Adverb),
-        ("Numeral", PartOfSpeech::Numeral),
-        ("Determiner", PartOfSpeech::Determiner),
-        ("Preposition", PartOfSpeech::Preposition),
-        ("Interjection", PartOfSpeech::Interjection),
-    ]);
+/// Parse a Wiktionary article body (the rendered HTML of a live `/wiki/{word}`
+/// page) into a `Word`. Used by [`wiktionary_lookup`]; the dump-ingestion path
+/// in the `dump` module reads raw wikitext with its own parser instead.
+pub fn parse_word_page(word: &str, lang: Language, body: &str, wiki_link: String) -> ResultOrError<Word> {
+    let strategy = strategy_for(lang);
+    let part_of_speech_name = strategy.pos_header_names();
 
-    let url = format!("https://en.wiktionary.org/wiki/{word}");
-    let res = request_w_header(url.as_str()).await?;
-    let body = res.text().await?;
-    let dom = tl::parse(body.as_str(), tl::ParserOptions::default())?;
+    let dom = tl::parse(body, tl::ParserOptions::default())?;
     let parser = dom.parser();
-    let sections = fetch_french_sections(&dom, parser).ok_or(format!("Cannot find word {word}"))?;
+    let sections = fetch_language_sections(&dom, parser, strategy.headline())
+        .ok_or(format!("Cannot find word {word}"))?;
     let mut pronunciation: Option<Pronunciation> = None;
     let mut meanings = Vec::<Meaning>::new();
     for section in sections {
@@ -356,8 +559,20 @@ pub async fn wiktionary_lookup(word: &str) -> ResultOrError<Word> {
     }
     Ok(Word {
         word: word.to_owned(),
-        wiki_link: format!("{url}#French"),
+        wiki_link,
         pronunciation,
         meanings,
     })
 }
+
+pub async fn wiktionary_lookup(
+    client: &reqwest::Client,
+    word: &str,
+    lang: Language,
+) -> ResultOrError<Word> {
+    let url = format!("https://en.wiktionary.org/wiki/{word}");
+    let res = request_w_header(client, url.as_str()).await?;
+    let body = res.text().await?;
+    let wiki_link = format!("{url}#{}", strategy_for(lang).headline());
+    parse_word_page(word, lang, &body, wiki_link)
+}
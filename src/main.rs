@@ -1,16 +1,96 @@
+use clap::{Parser, Subcommand};
+use futures::stream::{self, StreamExt};
 use glob::glob;
 use serde_json as json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 use std::path;
 use std::vec::Vec;
+mod dump;
+mod html;
 mod lookup;
+mod ordering;
+mod translate;
+use dump::{build_index_from_dump, load_index, save_index};
 use lookup::{
-    request_w_header, wiktionary_lookup, Example, Meaning, NounGender, PartOfSpeech, ResultOrError,
-    Word,
+    match_meaning, request_w_header, strategy_for, wiktionary_lookup, Example, Language,
+    ResultOrError, Word,
 };
 use regex::Regex;
+use translate::{fill_missing_translations, GoogleTranslator, Translator};
+
+/// How many words are looked up concurrently by default; overridable with `--concurrency`.
+const DEFAULT_CONCURRENCY: usize = 8;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum TranslatorBackend {
+    None,
+    Google,
+}
+
+/// Generate Anki flashcards for a word list by scraping Wiktionary.
+#[derive(Parser)]
+#[command(name = "anki-card-gen")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Look up a single word and dump it as JSON to stdout
+    Lookup {
+        word: String,
+        #[arg(long, value_enum, default_value = "french")]
+        lang: Language,
+    },
+    /// Scrape the words in `--glob`, download audio, and write cards
+    GenCards {
+        #[arg(long, default_value = "./words/*.txt")]
+        glob: String,
+        #[arg(long, default_value = "audio/")]
+        audio_dir: String,
+        #[arg(long, default_value = "words.json")]
+        out: String,
+        #[arg(long, value_enum, default_value = "french")]
+        lang: Language,
+        /// How many words to look up concurrently
+        #[arg(long, default_value_t = DEFAULT_CONCURRENCY)]
+        concurrency: usize,
+        /// Pre-built offline index (see `ingest-dump`) to resolve words from
+        /// before falling back to a live network lookup
+        #[arg(long)]
+        index: Option<String>,
+        /// Backend used to fill in example translations Wiktionary doesn't have;
+        /// "none" leaves them blank so offline runs don't need network access
+        #[arg(long, value_enum, default_value = "none")]
+        translator: TranslatorBackend,
+        /// Where to write a browsable HTML review deck alongside `--out`
+        #[arg(long, default_value = "deck.html")]
+        html: String,
+    },
+    /// Re-render the per-word-list `.txt` card files from an existing `words.json`
+    Format {
+        #[arg(long, default_value = "./words/*.txt")]
+        glob: String,
+        #[arg(long, default_value = "audio/")]
+        audio_dir: String,
+        #[arg(long, default_value = "words.json")]
+        json: String,
+        #[arg(long, value_enum, default_value = "french")]
+        lang: Language,
+    },
+    /// Build an offline word index from a Wiktionary `pages-articles` XML dump
+    IngestDump {
+        #[arg(long)]
+        dump: String,
+        #[arg(long, value_enum, default_value = "french")]
+        lang: Language,
+        #[arg(long, default_value = "wiktionary-index.json")]
+        out: String,
+    },
+}
 
 fn read_words_from(glob_pattern: &str) -> ResultOrError<HashMap<String, HashMap<String, String>>> {
     let mut words: HashMap<String, HashMap<String, String>> = HashMap::new();
@@ -48,86 +128,28 @@ fn read_words_from(glob_pattern: &str) -> ResultOrError<HashMap<String, HashMap<
 }
 
 async fn word_to_anki_fields(
+    client: &reqwest::Client,
     record: Word,
     select_meaning: &str,
     audio_dir: &str,
+    lang: Language,
+    chosen_example: Option<Example>,
 ) -> ResultOrError<Vec<String>> {
     fn format_example(example: Example) -> (String, String) {
         let (sentence, transl) = example;
         (sentence, transl.unwrap_or("".to_string()))
     }
 
-    fn format_genders(word: &str, meaning: &Meaning) -> String {
-        let first_ch = word.chars().nth(0).expect("Word is empty");
-        let is_vowel = match first_ch {
-            'a' | 'e' | 'i' | 'o' | 'u' | 'h' => true,
-            _ => false,
-        };
-        let is_masc = match meaning.pos {
-            PartOfSpeech::Noun {
-                gender: Some(NounGender::Masculine),
-            } => true,
-            PartOfSpeech::Noun {
-                gender: Some(NounGender::Feminine),
-            } => false,
-            _ => {
-                return "".to_string();
-            }
-        };
-        match (is_vowel, is_masc) {
-            (true, true) => format!("l'{word} (masc.)"),
-            (true, false) => format!("l'{word} (fem.)"),
-            (false, true) => format!("le {word}"),
-            (false, false) => format!("la {word}"),
-        }
-    }
-
-    fn match_meaning<'a>(
-        word: &str,
-        meanings: &'a Vec<Meaning>,
-        select_meaning: &str,
-    ) -> ResultOrError<&'a Meaning> {
-        let mut meaning = None;
-        let mut is_ambiguous = false;
-        let all_meanings_str = meanings
-            .iter()
-            .map(|m| "  ".to_string() + &m.meaning)
-            .collect::<Vec<_>>()
-            .join("\n");
-        for meaning_ in meanings {
-            let is_match = meaning_.meaning.contains(select_meaning);
-            if is_match {
-                if meaning.is_some() {
-                    is_ambiguous = true;
-                }
-                meaning = Some(meaning_);
-            }
-        }
-        if is_ambiguous {
-            println!("Ambiguous meaning '{select_meaning}' for word '{word}'; choose from \n{all_meanings_str}\n");
-        }
-        match meaning {
-            Some(meaning) => Ok(meaning),
-            None => {
-                println!(
-                    "No meaning of '{word}' matches the given meaning '{select_meaning}'. Select from:\n{all_meanings_str}");
-                Err(format!("No matching meaning for '{word}'"))?
-            }
-        }
-    }
-
     let word = record.word;
     let meanings = record.meanings;
     if meanings.len() == 0 {
         Err(format!("Word '{word}' without meaning is malformed"))?
     }
     let meaning = match_meaning(&word, &meanings, select_meaning)?;
-    let word_w_article = format_genders(&word, &meaning);
-    let examples = &meaning.examples;
-    let (ex_w_trans, ex_wo_trans) = if examples.len() > 0 {
-        format_example(examples[0].clone())
-    } else {
-        ("".to_string(), "".to_string())
+    let word_w_article = strategy_for(lang).format_article(&word, &meaning);
+    let (ex_w_trans, ex_wo_trans) = match chosen_example {
+        Some(example) => format_example(example),
+        None => ("".to_string(), "".to_string()),
     };
     let (ipa, audio_file_entry) = match record.pronunciation {
         Some(pronunciation) => {
@@ -135,7 +157,7 @@ async fn word_to_anki_fields(
             let path_str = format!("{audio_dir}/{word}.mp3");
             let path = path::Path::new(&path_str);
             if !path.exists() {
-                let audio = request_w_header(&audio_url).await?.bytes().await?;
+                let audio = request_w_header(client, &audio_url).await?.bytes().await?;
                 let mut audio_f = fs::File::create(path)?;
                 audio_f.write(&audio)?;
             }
@@ -158,37 +180,236 @@ async fn word_to_anki_fields(
     ])
 }
 
-async fn look_up_all(glob_pattern: &str, audio_dir: &str, json_f: &str) -> ResultOrError<()> {
+/// Where `look_up_all` reads prior results from and writes new ones to;
+/// bundled so the function itself doesn't grow a parameter per output format.
+struct LookupOutputs<'a> {
+    json_f: &'a str,
+    html_f: &'a str,
+    index_path: Option<&'a str>,
+    translator: Option<&'a dyn Translator>,
+}
+
+async fn look_up_all(
+    glob_pattern: &str,
+    audio_dir: &str,
+    lang: Language,
+    concurrency: usize,
+    outputs: LookupOutputs<'_>,
+) -> ResultOrError<()> {
+    let LookupOutputs {
+        json_f,
+        html_f,
+        index_path,
+        translator,
+    } = outputs;
     let to_look_up = read_words_from(glob_pattern)?;
-    let mut words = Vec::<Word>::new();
-    for (filename, to_look_up_) in to_look_up {
-        let mut out_f = fs::File::create(format!("{filename}.txt"))?;
+    let client = reqwest::Client::new();
+    let mut index = match index_path {
+        Some(path) => load_index(path)?,
+        None => HashMap::new(),
+    };
+    // Reuse a previous run's `words.json` (if any) as a cache, so already-fetched
+    // words, and any translations already filled into their examples, aren't
+    // re-requested; `--index` entries take precedence on a name collision.
+    if let Ok(content) = fs::read_to_string(json_f) {
+        if let Ok(cached_words) = json::from_str::<Vec<Word>>(&content) {
+            for word in cached_words {
+                index.entry(word.word.clone()).or_insert(word);
+            }
+        }
+    }
+
+    let mut flat: Vec<(String, String, String)> = Vec::new();
+    for (filename, to_look_up_) in &to_look_up {
         for (word_str, meaning) in to_look_up_ {
-            match wiktionary_lookup(&word_str).await {
-                Ok(word) => {
-                    words.push(word.clone());
-                    for field in word_to_anki_fields(word, &meaning, audio_dir).await? {
-                        out_f.write(field.as_bytes())?;
-                        out_f.write("\t".as_bytes())?;
+            flat.push((filename.clone(), word_str.clone(), meaning.clone()));
+        }
+    }
+
+    let mut looked_up: Vec<(String, String, String, ResultOrError<Word>)> =
+        stream::iter(flat)
+            .map(|(filename, word_str, meaning)| {
+                let client = &client;
+                let index = &index;
+                async move {
+                    let mut result = match index.get(&word_str) {
+                        Some(word) => Ok(word.clone()),
+                        None => wiktionary_lookup(client, &word_str, lang).await,
+                    };
+                    if let (Ok(word), Some(translator)) = (&mut result, translator) {
+                        fill_missing_translations(word, translator, lang).await;
                     }
-                    out_f.write("\n".as_bytes())?;
-                }
-                Err(err) => {
-                    println!("Failed to look up '{}' due to error '{}'", word_str, err);
-                    continue;
+                    (filename, word_str, meaning, result)
                 }
-            }
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+    // Sort by (filename, word) so output ordering doesn't depend on fetch completion order.
+    looked_up.sort_by(|(f1, w1, ..), (f2, w2, ..)| (f1, w1).cmp(&(f2, w2)));
+
+    let mut entries: Vec<(String, Word, String)> = Vec::new();
+    for (filename, word_str, meaning, result) in looked_up {
+        match result {
+            Ok(word) => entries.push((filename, word, meaning)),
+            Err(err) => println!("Failed to look up '{}' due to error '{}'", word_str, err),
+        }
+    }
+
+    let word_meaning_pairs: Vec<(Word, String)> = entries
+        .iter()
+        .map(|(_, word, meaning)| (word.clone(), meaning.clone()))
+        .collect();
+    let schedule = ordering::graduate(&word_meaning_pairs);
+
+    let vocab: HashSet<String> = to_look_up
+        .values()
+        .flat_map(|inner| inner.keys())
+        .map(|word| word.to_lowercase())
+        .collect();
+
+    let mut out_files: HashMap<String, fs::File> = to_look_up
+        .keys()
+        .map(|filename| Ok((filename.clone(), fs::File::create(format!("{filename}.txt"))?)))
+        .collect::<ResultOrError<_>>()?;
+    let mut words = Vec::<Word>::new();
+    let mut cards: Vec<(Word, String, Option<Example>)> = Vec::new();
+    for (idx, chosen_example) in schedule {
+        let (filename, word, meaning) = &entries[idx];
+        words.push(word.clone());
+        let out_f = out_files.get_mut(filename).expect("file was pre-created");
+        for field in word_to_anki_fields(
+            &client,
+            word.clone(),
+            meaning,
+            audio_dir,
+            lang,
+            chosen_example.clone(),
+        )
+        .await?
+        {
+            out_f.write(field.as_bytes())?;
+            out_f.write("\t".as_bytes())?;
         }
+        out_f.write("\n".as_bytes())?;
+        cards.push((word.clone(), meaning.clone(), chosen_example));
     }
     fs::write(json_f, json::to_string(&words)?)?;
+    html::write_deck(html_f, audio_dir, lang, &cards, &vocab)?;
+    Ok(())
+}
+
+/// Re-render the per-word-list `.txt` card files from a previously written `words.json`,
+/// without hitting the network again (audio is only re-downloaded if missing).
+/// Schedules cards with the same [`ordering::graduate`] pass `look_up_all` uses,
+/// so re-running `format` against a `gen-cards` run's own `words.json` reproduces
+/// the same example choice and card order rather than silently diverging.
+async fn format_cards(
+    glob_pattern: &str,
+    audio_dir: &str,
+    json_f: &str,
+    lang: Language,
+) -> ResultOrError<()> {
+    let to_look_up = read_words_from(glob_pattern)?;
+    let json_content = fs::read_to_string(json_f)?;
+    let words: Vec<Word> = json::from_str(&json_content)?;
+    let word_by_name: HashMap<String, Word> =
+        words.into_iter().map(|word| (word.word.clone(), word)).collect();
+
+    let mut entries: Vec<(String, Word, String)> = Vec::new();
+    for (filename, to_look_up_) in &to_look_up {
+        for (word_str, meaning) in to_look_up_ {
+            match word_by_name.get(word_str) {
+                Some(word) => entries.push((filename.clone(), word.clone(), meaning.clone())),
+                None => println!("Word '{}' not found in '{}'", word_str, json_f),
+            }
+        }
+    }
+    // Sort by (filename, word) so the schedule doesn't depend on HashMap iteration order.
+    entries.sort_by(|(f1, w1, ..), (f2, w2, ..)| (f1, &w1.word).cmp(&(f2, &w2.word)));
+
+    let word_meaning_pairs: Vec<(Word, String)> = entries
+        .iter()
+        .map(|(_, word, meaning)| (word.clone(), meaning.clone()))
+        .collect();
+    let schedule = ordering::graduate(&word_meaning_pairs);
+
+    let mut out_files: HashMap<String, fs::File> = to_look_up
+        .keys()
+        .map(|filename| Ok((filename.clone(), fs::File::create(format!("{filename}.txt"))?)))
+        .collect::<ResultOrError<_>>()?;
+    let client = reqwest::Client::new();
+    for (idx, chosen_example) in schedule {
+        let (filename, word, meaning) = &entries[idx];
+        let out_f = out_files.get_mut(filename).expect("file was pre-created");
+        for field in
+            word_to_anki_fields(&client, word.clone(), meaning, audio_dir, lang, chosen_example)
+                .await?
+        {
+            out_f.write(field.as_bytes())?;
+            out_f.write("\t".as_bytes())?;
+        }
+        out_f.write("\n".as_bytes())?;
+    }
     Ok(())
 }
 
 fn main() {
+    let cli = Cli::parse();
     let rt = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()
         .unwrap();
-    let result = look_up_all("./words/*.txt", "audio/", "words.json");
+    let result = async {
+        match cli.command {
+            Command::Lookup { word, lang } => {
+                let client = reqwest::Client::new();
+                let record = wiktionary_lookup(&client, &word, lang).await?;
+                println!("{}", json::to_string_pretty(&record)?);
+                Ok(())
+            }
+            Command::GenCards {
+                glob,
+                audio_dir,
+                out,
+                lang,
+                concurrency,
+                index,
+                translator,
+                html,
+            } => {
+                let translator: Option<Box<dyn Translator>> = match translator {
+                    TranslatorBackend::None => None,
+                    TranslatorBackend::Google => {
+                        Some(Box::new(GoogleTranslator::new(reqwest::Client::new())))
+                    }
+                };
+                look_up_all(
+                    &glob,
+                    &audio_dir,
+                    lang,
+                    concurrency,
+                    LookupOutputs {
+                        json_f: &out,
+                        html_f: &html,
+                        index_path: index.as_deref(),
+                        translator: translator.as_deref(),
+                    },
+                )
+                .await
+            }
+            Command::Format {
+                glob,
+                audio_dir,
+                json,
+                lang,
+            } => format_cards(&glob, &audio_dir, &json, lang).await,
+            Command::IngestDump { dump, lang, out } => {
+                let index = build_index_from_dump(&dump, lang)?;
+                println!("Indexed {} words from '{}'", index.len(), dump);
+                save_index(&index, &out)
+            }
+        }
+    };
     rt.block_on(result).unwrap();
 }
@@ -0,0 +1,152 @@
+//! Orders looked-up words into a "graduated" deck: each word's example
+//! sentence is chosen to need as little not-yet-studied vocabulary as
+//! possible, and words are scheduled so the set of introduced vocabulary
+//! only grows as the deck progresses.
+
+use crate::lookup::{match_meaning, Example, Word};
+use std::collections::HashSet;
+
+fn tokenize(sentence: &str) -> Vec<String> {
+    sentence
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+        .collect()
+}
+
+/// For each `(word, select_meaning)` pair, schedule cards in an order where
+/// every word's chosen example has the fewest tokens outside the
+/// already-introduced vocabulary (the lemmas of previously scheduled words).
+/// Ties go to whichever word has any example at all, then to the earlier
+/// entry in `entries`. Returns `(original_index, chosen_example)` pairs in
+/// schedule order; `chosen_example` is `None` for words without examples.
+pub fn graduate(entries: &[(Word, String)]) -> Vec<(usize, Option<Example>)> {
+    let candidate_examples: Vec<Vec<Example>> = entries
+        .iter()
+        .map(|(word, select_meaning)| {
+            match_meaning(&word.word, &word.meanings, select_meaning)
+                .map(|meaning| meaning.examples.clone())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut known = HashSet::new();
+    let mut scheduled = vec![false; entries.len()];
+    let mut order = Vec::with_capacity(entries.len());
+
+    for _ in 0..entries.len() {
+        let mut best: Option<(usize, Option<usize>, usize)> = None;
+        for (idx, examples) in candidate_examples.iter().enumerate() {
+            if scheduled[idx] {
+                continue;
+            }
+            let candidate = if examples.is_empty() {
+                (idx, None, usize::MAX)
+            } else {
+                let (example_idx, oov) = examples
+                    .iter()
+                    .enumerate()
+                    .map(|(example_idx, (orig, _))| {
+                        let oov = tokenize(orig).iter().filter(|t| !known.contains(*t)).count();
+                        (example_idx, oov)
+                    })
+                    .min_by_key(|&(_, oov)| oov)
+                    .unwrap();
+                (idx, Some(example_idx), oov)
+            };
+            best = Some(match best {
+                None => candidate,
+                Some(current) => {
+                    let candidate_is_better = candidate.2 < current.2
+                        || (candidate.2 == current.2 && candidate.1.is_some() && current.1.is_none());
+                    if candidate_is_better {
+                        candidate
+                    } else {
+                        current
+                    }
+                }
+            });
+        }
+        let (idx, example_idx, _) = best.expect("at least one unscheduled entry remains");
+        scheduled[idx] = true;
+        known.insert(entries[idx].0.word.to_lowercase());
+        let chosen = example_idx.map(|i| candidate_examples[idx][i].clone());
+        order.push((idx, chosen));
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lookup::{Meaning, PartOfSpeech};
+
+    fn word(name: &str, examples: Vec<Example>) -> Word {
+        Word {
+            word: name.to_string(),
+            wiki_link: String::new(),
+            pronunciation: None,
+            meanings: vec![Meaning {
+                pos: PartOfSpeech::Noun { gender: None },
+                meaning: "m".to_string(),
+                examples,
+            }],
+        }
+    }
+
+    fn entry(name: &str, examples: Vec<Example>) -> (Word, String) {
+        (word(name, examples), "m".to_string())
+    }
+
+    #[test]
+    fn tokenize_lowercases_and_splits_on_punctuation() {
+        assert_eq!(
+            tokenize("Le chat, c'est super!"),
+            vec!["le", "chat", "c", "est", "super"]
+        );
+    }
+
+    #[test]
+    fn graduate_prefers_fewer_oov_tokens_first() {
+        // "chat noir" has one OOV token ("noir") once "chat" is known;
+        // "chat chien noir" has two, so the former should graduate first.
+        let entries = vec![
+            entry("chat", vec![("chat".to_string(), None)]),
+            entry("noir", vec![("chat noir".to_string(), None)]),
+            entry("chien", vec![("chat chien noir".to_string(), None)]),
+        ];
+        let order: Vec<usize> = graduate(&entries).into_iter().map(|(idx, _)| idx).collect();
+        assert_eq!(order, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn graduate_breaks_same_oov_ties_by_earlier_entry() {
+        // Both examples are all-OOV (zero known vocabulary yet), so they tie
+        // on OOV count; the earlier entry should win the tie.
+        let entries = vec![
+            entry("un", vec![("un chat".to_string(), None)]),
+            entry("deux", vec![("deux chiens".to_string(), None)]),
+        ];
+        let order: Vec<usize> = graduate(&entries).into_iter().map(|(idx, _)| idx).collect();
+        assert_eq!(order, vec![0, 1]);
+    }
+
+    #[test]
+    fn graduate_schedules_entries_without_examples_last() {
+        // "sans_exemple" has no examples at all (oov treated as usize::MAX),
+        // so it should graduate after a word that has one, even a bad one.
+        let entries = vec![
+            entry("sans_exemple", vec![]),
+            entry("avec_exemple", vec![("avec exemple ici".to_string(), None)]),
+        ];
+        let order: Vec<usize> = graduate(&entries).into_iter().map(|(idx, _)| idx).collect();
+        assert_eq!(order, vec![1, 0]);
+    }
+
+    #[test]
+    fn graduate_returns_none_example_for_entries_without_examples() {
+        let entries = vec![entry("solo", vec![])];
+        let result = graduate(&entries);
+        assert_eq!(result, vec![(0, None)]);
+    }
+}
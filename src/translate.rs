@@ -0,0 +1,79 @@
+//! Pluggable backends for filling in example-sentence translations that
+//! Wiktionary itself doesn't provide (`parse_example` leaves `trans: None`
+//! whenever there's no `span.e-translation`).
+
+use crate::lookup::{Language, ResultOrError, Word};
+use async_trait::async_trait;
+use serde_json::Value;
+
+#[async_trait]
+pub trait Translator {
+    async fn translate(&self, text: &str, from: Language, to: Language) -> ResultOrError<String>;
+}
+
+fn lang_code(lang: Language) -> &'static str {
+    match lang {
+        Language::French => "fr",
+        Language::Spanish => "es",
+        Language::Italian => "it",
+        Language::German => "de",
+        Language::English => "en",
+    }
+}
+
+/// Drives the unauthenticated `translate.googleapis.com` endpoint, the same
+/// one tools like `translate-shell` use for a free Google Translate backend.
+pub struct GoogleTranslator {
+    client: reqwest::Client,
+}
+
+impl GoogleTranslator {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Translator for GoogleTranslator {
+    async fn translate(&self, text: &str, from: Language, to: Language) -> ResultOrError<String> {
+        let res = self
+            .client
+            .get("https://translate.googleapis.com/translate_a/single")
+            .query(&[
+                ("client", "gtx"),
+                ("sl", lang_code(from)),
+                ("tl", lang_code(to)),
+                ("dt", "t"),
+                ("q", text),
+            ])
+            .send()
+            .await?
+            .json::<Value>()
+            .await?;
+        let segments = res[0].as_array().ok_or("Unexpected translate response shape")?;
+        let translated = segments
+            .iter()
+            .filter_map(|segment| segment[0].as_str())
+            .collect::<String>();
+        Ok(translated)
+    }
+}
+
+/// Fill in every example in `word` whose translation is still `None`, so a
+/// later run that re-reads the cached `words.json` doesn't re-request it.
+pub async fn fill_missing_translations(word: &mut Word, translator: &dyn Translator, lang: Language) {
+    for meaning in &mut word.meanings {
+        for example in &mut meaning.examples {
+            if example.1.is_some() {
+                continue;
+            }
+            match translator.translate(&example.0, lang, Language::English).await {
+                Ok(translated) => example.1 = Some(translated),
+                Err(err) => println!(
+                    "Failed to translate example for '{}': {}",
+                    word.word, err
+                ),
+            }
+        }
+    }
+}